@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use piston_window::{Button, ButtonArgs, ButtonState, Event, GenericEvent, Input, Key, MouseButton};
+
+use crate::life::{CellType, Loc, World};
+use crate::sequencer::Sequencer;
+use crate::{Camera, Selection, Ticker};
+
+const ZOOM_SCROLL_FACTOR: f64 = 1.1;
+
+/// A keymap-level trigger: what a configured key *means*, before it's resolved
+/// into a concrete `Action` using the current cursor position and selection.
+/// Kept separate from `Action` so the keybinding map stays a plain, comparable,
+/// hashable value even though some actions need data the map can't hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    ToggleGridLines,
+    TogglePause,
+    Step,
+    SpeedUp,
+    SlowDown,
+    Copy,
+    Paste,
+    ClearRegion,
+    Save,
+    SequencerFaster,
+    SequencerSlower,
+}
+
+/// Maps keyboard keys to `Trigger`s, so controls are remappable in one place
+/// instead of being hard-coded into the event loop.
+pub struct Keybindings {
+    bindings: HashMap<Key, Trigger>,
+}
+
+impl Keybindings {
+    pub fn defaults() -> Self {
+        let mut bindings = HashMap::new();
+        bindings.insert(Key::G, Trigger::ToggleGridLines);
+        bindings.insert(Key::Space, Trigger::TogglePause);
+        bindings.insert(Key::Period, Trigger::Step);
+        bindings.insert(Key::Equals, Trigger::SpeedUp);
+        bindings.insert(Key::Minus, Trigger::SlowDown);
+        bindings.insert(Key::C, Trigger::Copy);
+        bindings.insert(Key::V, Trigger::Paste);
+        bindings.insert(Key::Delete, Trigger::ClearRegion);
+        bindings.insert(Key::S, Trigger::Save);
+        bindings.insert(Key::RightBracket, Trigger::SequencerFaster);
+        bindings.insert(Key::LeftBracket, Trigger::SequencerSlower);
+        Self { bindings }
+    }
+}
+
+/// A user-facing intent, decoupled from whatever raw input produced it. Adding
+/// an interactive feature means adding a variant here plus a case in `dispatch`,
+/// not more ad hoc event matching in the render loop.
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    PaintCell(Loc, CellType),
+    Pan([f64; 2]),
+    Zoom { cursor: [f64; 2], factor: f64 },
+    StartSelection(Loc),
+    DragSelection(Loc),
+    ToggleGridLines,
+    TogglePause,
+    Step,
+    SpeedUp,
+    SlowDown,
+    Copy,
+    Paste(Loc),
+    ClearRegion,
+    Save,
+    PaintMask(Loc),
+    EraseMask(Loc),
+    SequencerFaster,
+    SequencerSlower,
+}
+
+/// Tracks pressed buttons and the cursor position across frames, and
+/// translates raw Piston events (plus a keybinding map) into `Action`s.
+#[derive(Default)]
+pub struct InputState {
+    pub cursor: [f64; 2],
+    pub left_mouse_down: bool,
+    pub right_mouse_down: bool,
+    pub middle_mouse_down: bool,
+    pub shift_down: bool,
+    pub ctrl_down: bool,
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates button/cursor bookkeeping from one raw event and returns any
+    /// discrete `Action`s it triggers (pans, zooms, selection starts, keybindings).
+    /// Continuous actions driven by held buttons (painting, selection drag) come
+    /// from `frame_actions` instead, since they aren't tied to a single event.
+    pub fn handle_event(&mut self, e: &Event, keybindings: &Keybindings, camera: &Camera) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        if let Some(pos) = e.mouse_cursor_args() {
+            if self.middle_mouse_down {
+                let delta = [pos[0] - self.cursor[0], pos[1] - self.cursor[1]];
+                actions.push(Action::Pan(delta));
+            }
+            self.cursor = pos;
+        }
+
+        if let Some(scroll) = e.mouse_scroll_args() {
+            let factor = ZOOM_SCROLL_FACTOR.powf(scroll[1]);
+            actions.push(Action::Zoom { cursor: self.cursor, factor });
+        }
+
+        if let Event::Input(Input::Button(ButtonArgs { state, button: Button::Mouse(button), .. }), _) = e {
+            match button {
+                MouseButton::Left => {
+                    self.left_mouse_down = *state == ButtonState::Press;
+                    if self.left_mouse_down && self.shift_down {
+                        actions.push(Action::StartSelection(camera.screen_to_loc(self.cursor)));
+                    }
+                },
+                MouseButton::Right => self.right_mouse_down = *state == ButtonState::Press,
+                MouseButton::Middle => self.middle_mouse_down = *state == ButtonState::Press,
+                _ => {}
+            }
+        }
+
+        if let Event::Input(Input::Button(ButtonArgs { state, button: Button::Keyboard(key), .. }), _) = e {
+            match key {
+                Key::LShift | Key::RShift => self.shift_down = *state == ButtonState::Press,
+                Key::LCtrl | Key::RCtrl => self.ctrl_down = *state == ButtonState::Press,
+                _ if *state == ButtonState::Press => {
+                    if let Some(trigger) = keybindings.bindings.get(key) {
+                        actions.push(self.resolve_trigger(*trigger, camera));
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        actions
+    }
+
+    fn resolve_trigger(&self, trigger: Trigger, camera: &Camera) -> Action {
+        match trigger {
+            Trigger::ToggleGridLines => Action::ToggleGridLines,
+            Trigger::TogglePause => Action::TogglePause,
+            Trigger::Step => Action::Step,
+            Trigger::SpeedUp => Action::SpeedUp,
+            Trigger::SlowDown => Action::SlowDown,
+            Trigger::Copy => Action::Copy,
+            Trigger::Paste => Action::Paste(camera.screen_to_loc(self.cursor)),
+            Trigger::ClearRegion => Action::ClearRegion,
+            Trigger::Save => Action::Save,
+            Trigger::SequencerFaster => Action::SequencerFaster,
+            Trigger::SequencerSlower => Action::SequencerSlower,
+        }
+    }
+
+    /// Actions driven by buttons being *held* rather than a single event this
+    /// frame: continuous painting, and dragging out a selection.
+    pub fn frame_actions(&self, camera: &Camera) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        if self.shift_down && self.left_mouse_down {
+            actions.push(Action::DragSelection(camera.screen_to_loc(self.cursor)));
+        } else if self.ctrl_down && (self.left_mouse_down || self.right_mouse_down) {
+            let loc = camera.screen_to_loc(self.cursor);
+            if self.left_mouse_down {
+                actions.push(Action::PaintMask(loc));
+            } else {
+                actions.push(Action::EraseMask(loc));
+            }
+        } else if self.left_mouse_down || self.right_mouse_down {
+            // If both buttons are held, prioritize left (Red), matching the original brush.
+            let cell_type = if self.left_mouse_down { CellType::Red } else { CellType::Blue };
+            actions.push(Action::PaintCell(camera.screen_to_loc(self.cursor), cell_type));
+        }
+
+        actions
+    }
+}
+
+/// Bundles the mutable state an `Action` needs to act against, so `dispatch`
+/// doesn't need a long, ever-growing parameter list as features are added.
+pub struct ActionContext<'a> {
+    pub world: &'a mut World,
+    pub camera: &'a mut Camera,
+    pub selection: &'a mut Option<Selection>,
+    pub clipboard: &'a mut Vec<(Loc, CellType)>,
+    pub ticker: &'a mut Ticker,
+    pub show_lines: &'a mut bool,
+    pub sequencer: &'a mut Sequencer,
+}
+
+/// Executes a single `Action` against the given context.
+pub fn dispatch(action: Action, ctx: &mut ActionContext) {
+    match action {
+        Action::PaintCell(loc, cell_type) => {
+            // Paint a 2x2 block, matching the original brush size.
+            ctx.world.set_cell_now(&loc, cell_type);
+            ctx.world.set_cell_now(&Loc::new(loc.row + 1, loc.col), cell_type);
+            ctx.world.set_cell_now(&Loc::new(loc.row, loc.col + 1), cell_type);
+            ctx.world.set_cell_now(&Loc::new(loc.row + 1, loc.col + 1), cell_type);
+        },
+        Action::Pan(delta) => ctx.camera.pan_by_screen_delta(delta),
+        Action::Zoom { cursor, factor } => ctx.camera.zoom_about(cursor, factor),
+        Action::StartSelection(loc) => *ctx.selection = Some(Selection { start: loc, end: loc }),
+        Action::DragSelection(loc) => {
+            if let Some(sel) = ctx.selection.as_mut() {
+                sel.end = loc;
+            }
+        },
+        Action::ToggleGridLines => *ctx.show_lines = !*ctx.show_lines,
+        Action::TogglePause => ctx.ticker.toggle_pause(),
+        Action::Step => {
+            if ctx.ticker.paused {
+                let step_start = SystemTime::now();
+                ctx.world.step();
+                ctx.ticker.last_step_duration_ms = step_start.elapsed().map(|d| d.as_micros()).unwrap_or(0) as f64 / 1000.0;
+                ctx.ticker.last_queued_ticks = 1;
+            }
+        },
+        Action::SpeedUp => ctx.ticker.speed_up(),
+        Action::SlowDown => ctx.ticker.slow_down(),
+        Action::Copy => {
+            if let Some(sel) = ctx.selection.as_ref() {
+                let (min, max) = sel.min_max();
+                *ctx.clipboard = ctx.world.extract_region(min, max);
+            }
+        },
+        Action::Paste(loc) => ctx.world.stamp(loc, ctx.clipboard),
+        Action::ClearRegion => {
+            if let Some(sel) = ctx.selection.as_ref() {
+                let (min, max) = sel.min_max();
+                ctx.world.clear_region(min, max);
+            }
+        },
+        Action::Save => {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let filename = format!("life-{}.rle", timestamp);
+            match std::fs::write(&filename, ctx.world.to_rle()) {
+                Ok(()) => println!("Saved current generation to {}", filename),
+                Err(e) => eprintln!("Failed to save to {}: {}", filename, e),
+            }
+        },
+        Action::PaintMask(loc) => ctx.sequencer.mask.paint(loc),
+        Action::EraseMask(loc) => ctx.sequencer.mask.erase(loc),
+        Action::SequencerFaster => ctx.sequencer.speed_up(),
+        Action::SequencerSlower => ctx.sequencer.slow_down(),
+    }
+}
@@ -30,6 +30,94 @@ impl CellType {
     }
 }
 
+/// Birth/survival rules parsed from a standard rulestring like `"B3/S23"`
+/// (Conway's Life) or `"B36/S23"` (HighLife). `birth[n]` is true if a dead
+/// cell with `n` live neighbors is born; `survival[n]` is true if a live
+/// cell with `n` live neighbors survives. Indices run 0..=8, one per
+/// possible neighbor count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RuleSet {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl RuleSet {
+    /// Parses a rulestring of the form `B<digits>/S<digits>` (case-insensitive),
+    /// e.g. `"B3/S23"`, `"B36/S23"`, or `"B2/S"`.
+    pub fn parse(s: &str) -> Result<RuleSet, String> {
+        let trimmed = s.trim();
+        let parts: Vec<&str> = trimmed.splitn(2, '/').collect();
+        if parts.len() != 2 {
+            return Err(format!("Invalid rulestring '{}': expected format 'B<digits>/S<digits>'", trimmed));
+        }
+
+        let b_part = parts[0].trim();
+        let s_part = parts[1].trim();
+
+        let b_digits = b_part.strip_prefix('B').or_else(|| b_part.strip_prefix('b'))
+            .ok_or_else(|| format!("Invalid rulestring '{}': missing 'B' section", trimmed))?;
+        let s_digits = s_part.strip_prefix('S').or_else(|| s_part.strip_prefix('s'))
+            .ok_or_else(|| format!("Invalid rulestring '{}': missing 'S' section", trimmed))?;
+
+        Ok(RuleSet {
+            birth: Self::parse_digits(b_digits, trimmed)?,
+            survival: Self::parse_digits(s_digits, trimmed)?,
+        })
+    }
+
+    fn parse_digits(digits: &str, original: &str) -> Result<[bool; 9], String> {
+        let mut set = [false; 9];
+        for c in digits.chars() {
+            let n = c.to_digit(10)
+                .ok_or_else(|| format!("Invalid rulestring '{}': non-digit '{}' in neighbor-count list", original, c))?;
+            if n > 8 {
+                return Err(format!("Invalid rulestring '{}': neighbor count {} out of range 0-8", original, n));
+            }
+            if set[n as usize] {
+                return Err(format!("Invalid rulestring '{}': duplicate neighbor count {}", original, n));
+            }
+            set[n as usize] = true;
+        }
+        Ok(set)
+    }
+
+    /// Whether a dead cell with `alive_neighbors` live neighbors is born.
+    pub fn is_born(&self, alive_neighbors: usize) -> bool {
+        self.birth[alive_neighbors]
+    }
+
+    /// Whether a live cell with `alive_neighbors` live neighbors survives.
+    pub fn survives(&self, alive_neighbors: usize) -> bool {
+        self.survival[alive_neighbors]
+    }
+}
+
+impl Default for RuleSet {
+    /// Conway's Game of Life: `B3/S23`.
+    fn default() -> Self {
+        RuleSet::parse("B3/S23").unwrap()
+    }
+}
+
+impl std::fmt::Display for RuleSet {
+    /// Renders back to the rulestring notation `parse` accepts, e.g. `"B3/S23"`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "B")?;
+        for n in 0..9 {
+            if self.birth[n] {
+                write!(f, "{}", n)?;
+            }
+        }
+        write!(f, "/S")?;
+        for n in 0..9 {
+            if self.survival[n] {
+                write!(f, "{}", n)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(PartialEq,Eq,Hash,Clone,Copy)]
 pub struct Loc {
   pub row: i64,
@@ -62,30 +150,47 @@ pub struct World {
   buffer_1: HashMap<Loc, CellType>,
   buffer_2: HashMap<Loc, CellType>,
   using_buffer_1: bool,
+  rules: RuleSet,
 }
 
 impl World {
 
   pub fn new() -> World {
+    Self::new_with_rules(RuleSet::default())
+  }
+
+  /// Like [`World::new`], but with a non-default birth/survival [`RuleSet`].
+  pub fn new_with_rules(rules: RuleSet) -> World {
     Self {
       buffer_1: HashMap::new(),
       buffer_2: HashMap::new(),
       using_buffer_1: true,
+      rules,
     }
   }
 
+  /// Replaces the world's current rules (e.g. from a CLI override).
+  pub fn set_rules(&mut self, rules: RuleSet) {
+    self.rules = rules;
+  }
+
   /**
-   * Initialize from a configuration string. Assumes string is a grid of 
+   * Initialize from a configuration string. Assumes string is a grid of
    * periods and asterisks (rows separated by line breaks), where asterisks
    * are "alive" cells and periods are dead cells.
+   *
+   * The first line may instead be a rulestring header (e.g. `"B3/S23"`), in
+   * which case it is parsed into the world's `RuleSet` and excluded from the
+   * grid; otherwise the world defaults to Conway's rules.
    */
   pub fn from_configuration(data: &str, dead_char: char, alive_char: char) -> Result<Self,String> {
-    let mut world = Self::new();
+    let (rules, grid) = Self::split_rule_header(data)?;
+    let mut world = Self::new_with_rules(rules);
 
     let mut row = 0;
     let mut col = 0;
 
-    for c in data.chars() {
+    for c in grid.chars() {
       if c == dead_char {
         world.set(&Loc { row, col }, CellType::Dead);
         col += 1;
@@ -105,6 +210,175 @@ impl World {
     return Ok(world);
   }
 
+  /// Splits off an optional leading rulestring header line (e.g. `"B3/S23"`)
+  /// from a configuration string, returning the parsed `RuleSet` (or the
+  /// default if no header is present) and the remaining grid text.
+  fn split_rule_header(data: &str) -> Result<(RuleSet, &str), String> {
+    if let Some(newline) = data.find('\n') {
+      let first_line = data[..newline].trim();
+      if first_line.starts_with('B') || first_line.starts_with('b') {
+        let rules = RuleSet::parse(first_line)?;
+        return Ok((rules, &data[newline + 1..]));
+      }
+    }
+    Ok((RuleSet::default(), data))
+  }
+
+  /**
+   * Parses a run-length-encoded pattern: a header line like
+   * `"x = 10, y = 5, rule = B3/S23"` (comment lines starting with `#` are
+   * skipped; the rule clause is optional and defaults to Conway's rules),
+   * followed by a body of `<count><tag>` tokens where `b` is dead, `o` is a
+   * live Red cell, `x` is a live Blue cell, `$` ends a row, and `!` ends
+   * the pattern.
+   */
+  pub fn from_rle(data: &str) -> Result<Self, String> {
+    let mut header = None;
+    let mut body = String::new();
+
+    for line in data.lines() {
+      let trimmed = line.trim();
+      if trimmed.is_empty() || trimmed.starts_with('#') {
+        continue;
+      }
+      if header.is_none() {
+        header = Some(trimmed);
+      } else {
+        body.push_str(trimmed);
+      }
+    }
+
+    let header = header.ok_or_else(|| "RLE data is missing its header line".to_string())?;
+    let rules = Self::parse_rle_header(header)?;
+    let mut world = Self::new_with_rules(rules);
+
+    let mut row: i64 = 0;
+    let mut col: i64 = 0;
+    let mut count_digits = String::new();
+
+    for c in body.chars() {
+      if c.is_ascii_digit() {
+        count_digits.push(c);
+        continue;
+      }
+
+      let count = if count_digits.is_empty() {
+        1
+      } else {
+        count_digits.parse::<i64>().map_err(|_| format!("Invalid run count '{}'", count_digits))?
+      };
+      count_digits.clear();
+
+      match c {
+        'b' => col += count,
+        'o' => {
+          for i in 0..count {
+            world.set(&Loc::new(row, col + i), CellType::Red);
+          }
+          col += count;
+        },
+        'x' => {
+          for i in 0..count {
+            world.set(&Loc::new(row, col + i), CellType::Blue);
+          }
+          col += count;
+        },
+        '$' => {
+          row += count;
+          col = 0;
+        },
+        '!' => break,
+        other => return Err(format!("Invalid RLE token '{}'", other)),
+      }
+    }
+
+    Ok(world)
+  }
+
+  /// Extracts the optional `rule = ...` clause from an RLE header line,
+  /// defaulting to Conway's rules if none is present.
+  fn parse_rle_header(header: &str) -> Result<RuleSet, String> {
+    for part in header.split(',') {
+      if let Some(rule_str) = part.trim().strip_prefix("rule") {
+        let rule_str = rule_str.trim_start_matches(|c: char| c == '=' || c.is_whitespace());
+        return RuleSet::parse(rule_str.trim());
+      }
+    }
+    Ok(RuleSet::default())
+  }
+
+  /**
+   * Encodes the current generation as run-length-encoded text: a header
+   * (`"x = W, y = H, rule = ..."`) followed by the body, the inverse of
+   * `from_rle`. The bounding box is the tightest rectangle containing every
+   * live cell; trailing dead runs at the end of a row are omitted, as is
+   * conventional for RLE.
+   */
+  pub fn to_rle(&self) -> String {
+    let buffer = self.current_buffer();
+
+    let mut min_row = i64::MAX;
+    let mut max_row = i64::MIN;
+    let mut min_col = i64::MAX;
+    let mut max_col = i64::MIN;
+
+    for (loc, cell_type) in buffer.iter() {
+      if cell_type.is_alive() {
+        min_row = min_row.min(loc.row);
+        max_row = max_row.max(loc.row);
+        min_col = min_col.min(loc.col);
+        max_col = max_col.max(loc.col);
+      }
+    }
+
+    if min_row > max_row {
+      return format!("x = 0, y = 0, rule = {}\n!\n", self.rules);
+    }
+
+    let width = max_col - min_col + 1;
+    let height = max_row - min_row + 1;
+
+    let mut rows = Vec::new();
+    for row in min_row..=max_row {
+      let mut line = String::new();
+      let mut run_tag: Option<char> = None;
+      let mut run_len: i64 = 0;
+
+      for col in min_col..=max_col {
+        let tag = match self.get(&Loc::new(row, col)) {
+          CellType::Dead => 'b',
+          CellType::Red => 'o',
+          CellType::Blue => 'x',
+        };
+        if run_tag == Some(tag) {
+          run_len += 1;
+        } else {
+          if let Some(previous_tag) = run_tag {
+            Self::push_rle_run(&mut line, run_len, previous_tag);
+          }
+          run_tag = Some(tag);
+          run_len = 1;
+        }
+      }
+      // Dead cells trailing to the bounding box edge are implied, so omit them.
+      if let Some(tag) = run_tag {
+        if tag != 'b' {
+          Self::push_rle_run(&mut line, run_len, tag);
+        }
+      }
+      rows.push(line);
+    }
+
+    format!("x = {}, y = {}, rule = {}\n{}!\n", width, height, self.rules, rows.join("$"))
+  }
+
+  fn push_rle_run(out: &mut String, len: i64, tag: char) {
+    if len > 1 {
+      out.push_str(&len.to_string());
+    }
+    out.push(tag);
+  }
+
   pub fn current_buffer(&self) -> &HashMap<Loc, CellType> {
     if self.using_buffer_1 { 
       &self.buffer_1 
@@ -196,6 +470,47 @@ impl World {
     self.next_buffer().clear();
   }
 
+  /**
+   * Extracts the live cells within the inclusive rectangle `[min, max]`,
+   * with locations translated so the region's top-left (`min`) becomes
+   * `Loc::new(0, 0)`. Used to back copy/clipboard operations.
+   */
+  pub fn extract_region(&self, min: Loc, max: Loc) -> Vec<(Loc, CellType)> {
+    let mut cells = Vec::new();
+    for row in min.row..=max.row {
+      for col in min.col..=max.col {
+        let loc = Loc::new(row, col);
+        let cell_type = self.get(&loc);
+        if cell_type.is_alive() {
+          cells.push((Loc::new(row - min.row, col - min.col), cell_type));
+        }
+      }
+    }
+    cells
+  }
+
+  /**
+   * Pastes cells previously captured by `extract_region` back into the
+   * world, placing the region's former top-left at `origin`. Applied
+   * immediately via `set_cell_now`, same as a user click.
+   */
+  pub fn stamp(&mut self, origin: Loc, cells: &[(Loc, CellType)]) {
+    for (offset, cell_type) in cells {
+      self.set_cell_now(&Loc::new(origin.row + offset.row, origin.col + offset.col), *cell_type);
+    }
+  }
+
+  /**
+   * Kills every cell within the inclusive rectangle `[min, max]`.
+   */
+  pub fn clear_region(&mut self, min: Loc, max: Loc) {
+    for row in min.row..=max.row {
+      for col in min.col..=max.col {
+        self.set_cell_now(&Loc::new(row, col), CellType::Dead);
+      }
+    }
+  }
+
   /**
    * One "tick" of the world.
    */
@@ -235,13 +550,11 @@ impl World {
         
         let total_alive_neighbors = red_neighbors + blue_neighbors;
         
-        // Apply Conway's Game of Life rules to determine if the cell lives
+        // Apply the configured birth/survival rules to determine if the cell lives
         let will_be_alive = if current_alive {
-            // Live cell stays alive with 2 or 3 neighbors
-            total_alive_neighbors == 2 || total_alive_neighbors == 3
+            self.rules.survives(total_alive_neighbors)
         } else {
-            // Dead cell becomes alive with exactly 3 neighbors
-            total_alive_neighbors == 3
+            self.rules.is_born(total_alive_neighbors)
         };
         
         let next_type = if will_be_alive {
@@ -1,20 +1,176 @@
-use std::time::{SystemTime,UNIX_EPOCH};
+use std::time::SystemTime;
 use std::path::Path;
 
 extern crate piston_window;
 use piston_window::*;
 
 mod life;
-use life::{World, Loc, CellType};
+use life::{World, Loc, CellType, RuleSet};
+
+mod input;
+use input::{ActionContext, InputState, Keybindings};
+
+mod sequencer;
+use sequencer::{Sequencer, StdoutSink};
+
+const DEFAULT_BPM: f64 = 120.0;
 
 const BLACK: [f32;4] = [0.0, 0.0, 0.0, 1.0];
 const WHITE: [f32;4] = [1.0; 4];
 const RED: [f32;4] = [1.0, 0.0, 0.0, 1.0];
 const BLUE: [f32;4] = [0.0, 0.0, 1.0, 1.0];
+const GRID_LINE_COLOR: [f32;4] = [0.3, 0.3, 0.3, 1.0];
+const SELECTION_COLOR: [f32;4] = [1.0, 1.0, 0.0, 0.25];
+const MASK_COLOR: [f32;4] = [1.0, 1.0, 1.0, 0.35];
 const SQUARE_SIZE: f64 = 5.0;
 const WINDOW_SIZE: u32 = 1024;
 const GFX_CONTEXT_OFFSET: f64 = (WINDOW_SIZE / 2) as f64;
-const MILLIS_PER_FRAME: u128 = 10;
+// Below this on-screen cell size (in pixels), gridlines are too dense to be useful.
+const MIN_GRID_LINE_CELL_PX: f64 = 4.0;
+const MIN_ZOOM: f64 = 0.05;
+const MAX_ZOOM: f64 = 50.0;
+const OVERLAY_TEXT_COLOR: [f32;4] = [0.2, 1.0, 0.2, 1.0];
+const OVERLAY_FONT_SIZE: u32 = 14;
+
+const DEFAULT_STEPS_PER_SECOND: f64 = 100.0;
+const MIN_STEPS_PER_SECOND: f64 = 1.0;
+const MAX_STEPS_PER_SECOND: f64 = 10_000.0;
+const SPEED_CHANGE_FACTOR: f64 = 1.5;
+// If a slow frame would otherwise require running more ticks than this to catch
+// up, drop the backlog instead: better to visibly skip generations than spiral
+// further behind every subsequent frame.
+const MAX_QUEUED_TICKS: u32 = 30;
+
+/// Schedules `World::step()` calls at a target rate that is independent of
+/// the render frame rate, following the `Ticked` accumulator pattern: each
+/// frame contributes its elapsed time to an accumulator, and whole tick
+/// durations are drained off it (capped so a slow machine degrades
+/// gracefully rather than running an ever-growing backlog).
+struct Ticker {
+    steps_per_second: f64,
+    accumulated_secs: f64,
+    paused: bool,
+    last_step_duration_ms: f64,
+    last_queued_ticks: u32,
+}
+
+impl Ticker {
+    fn new() -> Self {
+        Self {
+            steps_per_second: DEFAULT_STEPS_PER_SECOND,
+            accumulated_secs: 0.0,
+            paused: false,
+            last_step_duration_ms: 0.0,
+            last_queued_ticks: 0,
+        }
+    }
+
+    fn tick_duration_secs(&self) -> f64 {
+        1.0 / self.steps_per_second
+    }
+
+    /// Advances the accumulator by `dt` seconds and returns how many ticks
+    /// should run this frame, draining their time from the accumulator.
+    fn queued_ticks(&mut self, dt: f64) -> u32 {
+        if self.paused {
+            return 0;
+        }
+
+        self.accumulated_secs += dt;
+        let tick_duration = self.tick_duration_secs();
+        let mut queued = (self.accumulated_secs / tick_duration).floor() as u32;
+
+        if queued > MAX_QUEUED_TICKS {
+            queued = MAX_QUEUED_TICKS;
+            self.accumulated_secs = 0.0;
+        } else {
+            self.accumulated_secs -= queued as f64 * tick_duration;
+        }
+
+        self.last_queued_ticks = queued;
+        queued
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    fn speed_up(&mut self) {
+        self.steps_per_second = (self.steps_per_second * SPEED_CHANGE_FACTOR).min(MAX_STEPS_PER_SECOND);
+    }
+
+    fn slow_down(&mut self) {
+        self.steps_per_second = (self.steps_per_second / SPEED_CHANGE_FACTOR).max(MIN_STEPS_PER_SECOND);
+    }
+}
+
+/// Pan/zoom state for the viewport onto the (unbounded) world.
+///
+/// `pan` is stored in world units (cells) so that it composes naturally with
+/// `zoom`: screen-to-world conversion divides by `SQUARE_SIZE * zoom` and
+/// then subtracts `pan`, while world-to-screen multiplies by the same
+/// factor and adds `pan` first. Keeping both conversions in terms of the
+/// same two numbers is what keeps drawing and display in sync at any scale.
+struct Camera {
+    pan: [f64; 2],
+    zoom: f64,
+}
+
+impl Camera {
+    fn new() -> Self {
+        Self { pan: [0.0, 0.0], zoom: 1.0 }
+    }
+
+    /// Converts a window-space position (as reported by Piston) into a world `Loc`.
+    fn screen_to_loc(&self, pos: [f64; 2]) -> Loc {
+        let scale = SQUARE_SIZE * self.zoom;
+        let col = ((pos[0] - GFX_CONTEXT_OFFSET) / scale - self.pan[0]).floor() as i64;
+        let row = ((pos[1] - GFX_CONTEXT_OFFSET) / scale - self.pan[1]).floor() as i64;
+        Loc::new(row, col)
+    }
+
+    /// Converts a window-space position into continuous (unfloored) world coordinates.
+    fn screen_to_world(&self, pos: [f64; 2]) -> [f64; 2] {
+        let scale = SQUARE_SIZE * self.zoom;
+        [
+            (pos[0] - GFX_CONTEXT_OFFSET) / scale - self.pan[0],
+            (pos[1] - GFX_CONTEXT_OFFSET) / scale - self.pan[1],
+        ]
+    }
+
+    /// Pans the camera by a drag delta expressed in screen pixels.
+    fn pan_by_screen_delta(&mut self, delta: [f64; 2]) {
+        let scale = SQUARE_SIZE * self.zoom;
+        self.pan[0] += delta[0] / scale;
+        self.pan[1] += delta[1] / scale;
+    }
+
+    /// Multiplies the zoom by `factor`, keeping the world point under `cursor` fixed.
+    fn zoom_about(&mut self, cursor: [f64; 2], factor: f64) {
+        let world_before = self.screen_to_world(cursor);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let scale = SQUARE_SIZE * self.zoom;
+        self.pan[0] = (cursor[0] - GFX_CONTEXT_OFFSET) / scale - world_before[0];
+        self.pan[1] = (cursor[1] - GFX_CONTEXT_OFFSET) / scale - world_before[1];
+    }
+}
+
+/// A rectangular region the user has dragged out with Shift+left-drag,
+/// in world cell coordinates. `start` and `end` are the drag endpoints in
+/// whichever order the drag happened, so callers go through `min_max`.
+struct Selection {
+    start: Loc,
+    end: Loc,
+}
+
+impl Selection {
+    /// Normalizes the drag endpoints into an inclusive `(min, max)` rectangle.
+    fn min_max(&self) -> (Loc, Loc) {
+        let min = Loc::new(self.start.row.min(self.end.row), self.start.col.min(self.end.col));
+        let max = Loc::new(self.start.row.max(self.end.row), self.start.col.max(self.end.col));
+        (min, max)
+    }
+}
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -29,92 +185,89 @@ fn main() {
             .build()
             .unwrap();
 
-        let configuration_path = String::from("./src/configurations/") + &args[1] + ".txt";
-        let mut world = World::from_configuration(&std::fs::read_to_string(Path::new(&configuration_path)).unwrap(), '.', '*').unwrap();
+        // A bare name resolves to a dense-grid config under src/configurations/;
+        // a path with its own extension (e.g. a previously saved .rle snapshot)
+        // is read as-is, so saved patterns can be passed straight back in.
+        let configuration_path = if args[1].contains('.') {
+            args[1].clone()
+        } else {
+            String::from("./src/configurations/") + &args[1] + ".txt"
+        };
+        let configuration_data = std::fs::read_to_string(Path::new(&configuration_path)).unwrap();
+        let mut world = if configuration_path.ends_with(".rle") {
+            World::from_rle(&configuration_data).unwrap()
+        } else {
+            World::from_configuration(&configuration_data, '.', '*').unwrap()
+        };
+
+        // A rulestring supplied on the command line overrides any header in the file.
+        if let Some(rule_arg) = args.get(2) {
+            let rules = RuleSet::parse(rule_arg).unwrap_or_else(|e| panic!("{}", e));
+            world.set_rules(rules);
+        }
+
         world.swap_buffers_and_clear();
 
-        let mut previous_update = UNIX_EPOCH;
-        let mut last_mouse_pos = [0.0, 0.0]; // Variable to store mouse position
-        let mut is_left_mouse_down = false; // Track if left mouse button is held
-        let mut is_right_mouse_down = false; // Track if right mouse button is held
+        let mut glyphs = window.load_font("assets/FiraSans-Regular.ttf").unwrap();
+
+        let mut ticker = Ticker::new();
+        let mut camera = Camera::new();
+        let mut show_lines = false;
+        let mut selection: Option<Selection> = None;
+        let mut clipboard: Vec<(Loc, CellType)> = Vec::new();
+
+        let mut input_state = InputState::new();
+        let keybindings = Keybindings::defaults();
+
+        let mut sequencer = Sequencer::new(DEFAULT_BPM);
+        let mut sink = StdoutSink::new();
 
         while let Some(e) = window.next() {
-            // --- Store Mouse Position ---
-            if let Some(pos) = e.mouse_cursor_args() {
-                last_mouse_pos = pos;
+            for action in input_state.handle_event(&e, &keybindings, &camera) {
+                input::dispatch(action, &mut ActionContext {
+                    world: &mut world,
+                    camera: &mut camera,
+                    selection: &mut selection,
+                    clipboard: &mut clipboard,
+                    ticker: &mut ticker,
+                    show_lines: &mut show_lines,
+                    sequencer: &mut sequencer,
+                });
             }
-            // --- End Store Mouse Position ---
-
-            // --- Handle Mouse Button State ---
-            if let Event::Input(Input::Button(ButtonArgs {
-                state, // Capture the state (Press or Release)
-                button: Button::Mouse(button),
-                scancode: _, 
-            }), _timestamp) = e
-            {
-                match button {
-                    MouseButton::Left => {
-                        is_left_mouse_down = state == ButtonState::Press;
-                        println!("Left mouse button: {}", if is_left_mouse_down { "pressed" } else { "released" });
-                    },
-                    MouseButton::Right => {
-                        is_right_mouse_down = state == ButtonState::Press;
-                        println!("Right mouse button: {}", if is_right_mouse_down { "pressed" } else { "released" });
-                    },
-                    _ => {}
-                }
-            }
-            // --- End Mouse Button State ---
-
-            // --- Continuous Drawing while Mouse Down ---
-            if is_left_mouse_down || is_right_mouse_down {
-                // Use the stored mouse position
-                let pos = last_mouse_pos;
-
-                // Convert window coordinates [x, y] to world coordinates Loc { row, col }
-                let world_x = pos[0] - GFX_CONTEXT_OFFSET;
-                let world_y = pos[1] - GFX_CONTEXT_OFFSET;
-
-                // Divide by square size and floor to get cell coordinates
-                let col = (world_x / SQUARE_SIZE).floor() as i64;
-                let row = (world_y / SQUARE_SIZE).floor() as i64;
-
-                // Set cell type based on which mouse button is pressed
-                let cell_type = if is_left_mouse_down && is_right_mouse_down {
-                    // If both buttons are pressed, prioritize left (Red)
-                    CellType::Red
-                } else if is_left_mouse_down {
-                    CellType::Red
-                } else if is_right_mouse_down {
-                    CellType::Blue
-                } else {
-                    // This shouldn't happen given our if condition above, but just in case
-                    CellType::Red
-                };
-
-                // Set a 2x2 block of cells with the appropriate type
-                println!("Setting cells at ({}, {}) with type: {:?}", row, col, cell_type);
-                world.set_cell_now(&Loc::new(row, col), cell_type);
-                world.set_cell_now(&Loc::new(row + 1, col), cell_type);
-                world.set_cell_now(&Loc::new(row, col + 1), cell_type);
-                world.set_cell_now(&Loc::new(row + 1, col + 1), cell_type);
+
+            for action in input_state.frame_actions(&camera) {
+                input::dispatch(action, &mut ActionContext {
+                    world: &mut world,
+                    camera: &mut camera,
+                    selection: &mut selection,
+                    clipboard: &mut clipboard,
+                    ticker: &mut ticker,
+                    show_lines: &mut show_lines,
+                    sequencer: &mut sequencer,
+                });
             }
-            // --- End Continuous Drawing ---
-
-            if previous_update.elapsed().map(|d| d.as_millis()).unwrap_or(0) > MILLIS_PER_FRAME {
-                // NOTE: Uncomment for timing info
-                // let step_start = SystemTime::now();
-                world.step();
-                // println!("Step took: {}ms", step_start.elapsed().map(|d| d.as_micros()).unwrap_or(0) as f32 / 1000.0);
-                previous_update = SystemTime::now();
+
+            if let Some(update) = e.update_args() {
+                let queued = ticker.queued_ticks(update.dt);
+                for _ in 0..queued {
+                    let step_start = SystemTime::now();
+                    world.step();
+                    ticker.last_step_duration_ms = step_start.elapsed().map(|d| d.as_micros()).unwrap_or(0) as f64 / 1000.0;
+                }
+                sequencer.advance(update.dt, &world, &mut sink);
             }
-            
-            window.draw_2d(&e, |context, graphics, _| {
+
+            window.draw_2d(&e, |context, graphics, device| {
                 clear(BLACK, graphics);
 
-                // Translate by 1/2 the window size, to center 0,0 in the middle of the window
-                let context = context.trans(GFX_CONTEXT_OFFSET, GFX_CONTEXT_OFFSET);
-                
+                // Translate by 1/2 the window size (to center 0,0 in the window) and
+                // apply the camera zoom; all world drawing below uses unzoomed pixel
+                // coordinates (cell position plus pan, times SQUARE_SIZE), while the
+                // original `context` stays in screen space for overlay text.
+                let world_context = context.trans(GFX_CONTEXT_OFFSET, GFX_CONTEXT_OFFSET).zoom(camera.zoom);
+
+                let cell_size = SQUARE_SIZE;
+
                 // Use iter() to get key and value directly, avoiding extra get() lookup
                 for (loc, cell_type) in world.current_buffer().iter() {
                     if cell_type.is_alive() {
@@ -123,10 +276,68 @@ fn main() {
                             CellType::Blue => BLUE,
                             CellType::Dead => WHITE, // Should never happen due to is_alive() check
                         };
-                        rectangle(color, [loc.col as f64 * SQUARE_SIZE, loc.row as f64 * SQUARE_SIZE, SQUARE_SIZE, SQUARE_SIZE], context.transform, graphics);
+                        let x = (loc.col as f64 + camera.pan[0]) * cell_size;
+                        let y = (loc.row as f64 + camera.pan[1]) * cell_size;
+                        rectangle(color, [x, y, cell_size, cell_size], world_context.transform, graphics);
                     }
                 }
+
+                // Gridlines are only worth drawing once cells are a few pixels across on screen.
+                if show_lines && cell_size * camera.zoom >= MIN_GRID_LINE_CELL_PX {
+                    let top_left = camera.screen_to_world([0.0, 0.0]);
+                    let bottom_right = camera.screen_to_world([WINDOW_SIZE as f64, WINDOW_SIZE as f64]);
+
+                    let first_col = top_left[0].floor() as i64;
+                    let last_col = bottom_right[0].ceil() as i64;
+                    let first_row = top_left[1].floor() as i64;
+                    let last_row = bottom_right[1].ceil() as i64;
+
+                    for col in first_col..=last_col {
+                        let x = (col as f64 + camera.pan[0]) * cell_size;
+                        let y0 = (first_row as f64 + camera.pan[1]) * cell_size;
+                        let y1 = (last_row as f64 + camera.pan[1] + 1.0) * cell_size;
+                        line(GRID_LINE_COLOR, 0.5, [x, y0, x, y1], world_context.transform, graphics);
+                    }
+                    for row in first_row..=last_row {
+                        let y = (row as f64 + camera.pan[1]) * cell_size;
+                        let x0 = (first_col as f64 + camera.pan[0]) * cell_size;
+                        let x1 = (last_col as f64 + camera.pan[0] + 1.0) * cell_size;
+                        line(GRID_LINE_COLOR, 0.5, [x0, y, x1, y], world_context.transform, graphics);
+                    }
+                }
+
+                // Draw the in-progress/most recent selection rectangle, if any.
+                if let Some(sel) = &selection {
+                    let (min, max) = sel.min_max();
+                    let x = (min.col as f64 + camera.pan[0]) * cell_size;
+                    let y = (min.row as f64 + camera.pan[1]) * cell_size;
+                    let w = (max.col - min.col + 1) as f64 * cell_size;
+                    let h = (max.row - min.row + 1) as f64 * cell_size;
+                    rectangle(SELECTION_COLOR, [x, y, w, h], world_context.transform, graphics);
+                }
+
+                // Cells the sequencer is watching, so the mask stays visible while editing it.
+                for loc in sequencer.mask.cells() {
+                    let x = (loc.col as f64 + camera.pan[0]) * cell_size;
+                    let y = (loc.row as f64 + camera.pan[1]) * cell_size;
+                    rectangle(MASK_COLOR, [x, y, cell_size, cell_size], world_context.transform, graphics);
+                }
+
+                // Timing/status overlay: drawn in screen space, ignoring the camera transform.
+                let population = world.current_buffer().values().filter(|c| c.is_alive()).count();
+                let status = format!(
+                    "{}{:.1} sps | step {:.2}ms | queued {} | alive {}",
+                    if ticker.paused { "PAUSED | " } else { "" },
+                    ticker.steps_per_second,
+                    ticker.last_step_duration_ms,
+                    ticker.last_queued_ticks,
+                    population,
+                );
+                text::Text::new_color(OVERLAY_TEXT_COLOR, OVERLAY_FONT_SIZE)
+                    .draw(&status, &mut glyphs, &context.draw_state, context.trans(10.0, 20.0).transform, graphics)
+                    .unwrap();
+                glyphs.factory.encoder.flush(device);
             });
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use crate::life::{CellType, Loc, World};
+
+const MIN_BPM: f64 = 1.0;
+const MAX_BPM: f64 = 1000.0;
+const BPM_CHANGE_FACTOR: f64 = 1.25;
+
+/// Cells the user has painted as "watched", independent of the simulation's
+/// own state. The `Sequencer` scans this set against the world each beat to
+/// decide what's currently sounding.
+#[derive(Default)]
+pub struct Mask {
+    cells: HashSet<Loc>,
+}
+
+impl Mask {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn paint(&mut self, loc: Loc) {
+        self.cells.insert(loc);
+    }
+
+    pub fn erase(&mut self, loc: Loc) {
+        self.cells.remove(&loc);
+    }
+
+    /// Iterates the watched cells, e.g. to render them as an overlay.
+    pub fn cells(&self) -> impl Iterator<Item = &Loc> {
+        self.cells.iter()
+    }
+
+    /// Returns the watched cells that are currently alive in `world`.
+    fn active_cells(&self, world: &World) -> Vec<(Loc, CellType)> {
+        self.cells.iter()
+            .map(|loc| (*loc, world.get(loc)))
+            .filter(|(_, cell_type)| cell_type.is_alive())
+            .collect()
+    }
+}
+
+/// A backend that turns mask activity into musical (or other) events. New
+/// backends plug in by implementing this trait; `Sequencer` doesn't care
+/// which one it's talking to.
+pub trait EventSink {
+    fn emit(&mut self, active: &[(Loc, CellType)], generation: u64);
+}
+
+/// Logs active mask cells to stdout, mapping a cell's row to a pitch and its
+/// `CellType` to one of two instrument/channel groups.
+pub struct StdoutSink;
+
+impl StdoutSink {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Wraps a row into a two-octave pitch range, so an unbounded world still
+    /// produces sensible notes.
+    fn pitch_for_row(row: i64) -> i64 {
+        60 + row.rem_euclid(24)
+    }
+
+    fn channel_for(cell_type: CellType) -> &'static str {
+        match cell_type {
+            CellType::Red => "lead",
+            CellType::Blue => "bass",
+            CellType::Dead => "none",
+        }
+    }
+}
+
+impl EventSink for StdoutSink {
+    fn emit(&mut self, active: &[(Loc, CellType)], generation: u64) {
+        for (loc, cell_type) in active {
+            println!(
+                "gen {} | {} | pitch {} (row {}, col {})",
+                generation,
+                Self::channel_for(*cell_type),
+                Self::pitch_for_row(loc.row),
+                loc.row,
+                loc.col,
+            );
+        }
+    }
+}
+
+/// Scans a `Mask` against the world on every beat and forwards active cells
+/// to an `EventSink`, advancing at its own BPM independent of the
+/// simulation's steps-per-second. This turns Life into a generative pattern
+/// source: cells don't just live and die, they play.
+pub struct Sequencer {
+    pub mask: Mask,
+    bpm: f64,
+    accumulated_secs: f64,
+    generation: u64,
+}
+
+impl Sequencer {
+    pub fn new(bpm: f64) -> Self {
+        Self {
+            mask: Mask::new(),
+            bpm,
+            accumulated_secs: 0.0,
+            generation: 0,
+        }
+    }
+
+    fn beat_duration_secs(&self) -> f64 {
+        60.0 / self.bpm
+    }
+
+    pub fn speed_up(&mut self) {
+        self.bpm = (self.bpm * BPM_CHANGE_FACTOR).min(MAX_BPM);
+    }
+
+    pub fn slow_down(&mut self) {
+        self.bpm = (self.bpm / BPM_CHANGE_FACTOR).max(MIN_BPM);
+    }
+
+    /// Advances by `dt` seconds and, for every beat elapsed, scans the mask
+    /// against `world` and forwards the active cells to `sink`.
+    pub fn advance(&mut self, dt: f64, world: &World, sink: &mut dyn EventSink) {
+        self.accumulated_secs += dt;
+        let beat_duration = self.beat_duration_secs();
+
+        while self.accumulated_secs >= beat_duration {
+            self.accumulated_secs -= beat_duration;
+            self.generation += 1;
+            let active = self.mask.active_cells(world);
+            sink.emit(&active, self.generation);
+        }
+    }
+}